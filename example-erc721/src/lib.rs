@@ -15,12 +15,25 @@ use frame_support::dispatch::DispatchResultWithPostInfo;
 
 type TokenId = U256;
 
+/// Identifies an NFT collection. Shares the 32-byte resource id convention used elsewhere in the
+/// bridge so a collection can be mapped 1:1 onto a bridged resource.
+type CollectionId = [u8; 32];
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct Erc721Token {
     pub id: TokenId,
+    pub collection_id: CollectionId,
     pub metadata: Vec<u8>,
 }
 
+/// Descriptive metadata for an NFT collection, analogous to an ERC-721 contract's name/symbol.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct CollectionProperties {
+    pub name: Vec<u8>,
+    pub symbol: Vec<u8>,
+    pub base_uri: Vec<u8>,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::pallet_prelude::*;
@@ -55,6 +68,8 @@ pub mod pallet {
         Transferred(<T as frame_system::Config>::AccountId, <T as frame_system::Config>::AccountId, TokenId),
         /// Token removed from the system
         Burned(TokenId),
+        /// A new collection was created
+        CollectionCreated(CollectionId),
     }
 
     #[pallet::error]
@@ -65,6 +80,8 @@ pub mod pallet {
         TokenAlreadyExists,
         /// Origin is not owner
         NotOwner,
+        /// No `CollectionProperties` exists for this collection id
+        CollectionDoesNotExist,
     }
 
     #[pallet::storage]
@@ -85,6 +102,27 @@ pub mod pallet {
         T::AccountId
     >;
 
+    /// Descriptive metadata (name/symbol/base URI) for each NFT collection.
+    #[pallet::storage]
+    #[pallet::getter(fn collections)]
+    pub(super) type Collections<T: Config> = StorageMap<
+        _,
+        Blake2_256,
+        CollectionId,
+        CollectionProperties
+    >;
+
+    /// Index of tokens by owner, maintained alongside `TokenOwner` so `tokens_of` is O(owned)
+    /// instead of a full scan over `Tokens`.
+    #[pallet::storage]
+    pub(super) type OwnerTokens<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_256,
+        T::AccountId,
+        Blake2_128Concat,
+        TokenId,
+        ()
+    >;
 
     #[pallet::type_value]
     pub(super) fn TokenCountDefault<T: Config>() -> U256 {
@@ -106,11 +144,26 @@ pub mod pallet {
         #[pallet::weight(195_000_000)]
         pub fn mint(
             origin: OriginFor<T>,
-            owner: T::AccountId, id: TokenId, metadata: Vec<u8>
+            owner: T::AccountId, collection_id: CollectionId, id: TokenId, metadata: Vec<u8>
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+
+            Self::mint_token(owner, collection_id, id, metadata)?;
+
+            Ok(().into())
+        }
+
+        /// Creates a new NFT collection with the given id and descriptive metadata.
+        #[pallet::weight(195_000_000)]
+        pub fn create_collection(
+            origin: OriginFor<T>,
+            collection_id: CollectionId,
+            props: CollectionProperties
         ) -> DispatchResultWithPostInfo {
             ensure_root(origin)?;
 
-            Self::mint_token(owner, id, metadata)?;
+            <Collections<T>>::insert(&collection_id, props);
+            Self::deposit_event(Event::CollectionCreated(collection_id));
 
             Ok(().into())
         }
@@ -147,18 +200,55 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
-    /// Creates a new token in the system.
+    /// Creates a new token in the system, under an already-created collection.
     pub fn mint_token(
         owner: T::AccountId,
+        collection_id: CollectionId,
         id: TokenId,
         metadata: Vec<u8>
     ) -> DispatchResultWithPostInfo {
         ensure!(!<Tokens<T>>::contains_key(id), Error::<T>::TokenAlreadyExists);
+        ensure!(<Collections<T>>::contains_key(collection_id), Error::<T>::CollectionDoesNotExist);
+
+        Self::insert_token(owner, collection_id, id, metadata)
+    }
 
-        let new_token = Erc721Token { id, metadata };
+    /// Mints a token into `collection_id`, registering an empty placeholder collection first if
+    /// one doesn't already exist.
+    ///
+    /// Used on the bridge-inbound mint path: by the time this runs, the token has already been
+    /// burned on the source chain, so failing here for a missing `create_collection` call would
+    /// destroy the asset with no way to recover it. Operators should still call
+    /// `create_collection` with real metadata for every resource they bridge in; this is a
+    /// safety net, not a substitute for registering collections properly.
+    pub fn mint_token_or_create_collection(
+        owner: T::AccountId,
+        collection_id: CollectionId,
+        id: TokenId,
+        metadata: Vec<u8>
+    ) -> DispatchResultWithPostInfo {
+        ensure!(!<Tokens<T>>::contains_key(id), Error::<T>::TokenAlreadyExists);
+
+        if !<Collections<T>>::contains_key(collection_id) {
+            let placeholder = CollectionProperties { name: Vec::new(), symbol: Vec::new(), base_uri: Vec::new() };
+            <Collections<T>>::insert(&collection_id, placeholder);
+            Self::deposit_event(Event::CollectionCreated(collection_id));
+        }
+
+        Self::insert_token(owner, collection_id, id, metadata)
+    }
+
+    fn insert_token(
+        owner: T::AccountId,
+        collection_id: CollectionId,
+        id: TokenId,
+        metadata: Vec<u8>
+    ) -> DispatchResultWithPostInfo {
+        let new_token = Erc721Token { id, collection_id, metadata };
 
         <Tokens<T>>::insert(&id, new_token);
         <TokenOwner<T>>::insert(&id, owner.clone());
+        <OwnerTokens<T>>::insert(&owner, &id, ());
         let new_total = <TokenCount<T>>::get().saturating_add(U256::one());
         <TokenCount<T>>::put(new_total);
 
@@ -178,6 +268,8 @@ impl<T: Config> Pallet<T> {
         ensure!(owner == from, Error::<T>::NotOwner);
         // Update owner
         <TokenOwner<T>>::insert(&id, to.clone());
+        <OwnerTokens<T>>::remove(&from, &id);
+        <OwnerTokens<T>>::insert(&to, &id, ());
 
         Self::deposit_event(Event::Transferred(from, to, id));
 
@@ -194,11 +286,18 @@ impl<T: Config> Pallet<T> {
 
         <Tokens<T>>::remove(&id);
         <TokenOwner<T>>::remove(&id);
-        let new_total = <TokenCount<T>>::get().saturating_add(U256::one());
+        <OwnerTokens<T>>::remove(&from, &id);
+        let new_total = <TokenCount<T>>::get().saturating_sub(U256::one());
         <TokenCount<T>>::put(new_total);
 
         Self::deposit_event(Event::Burned(id));
 
         Ok(().into())
     }
+
+    /// Lists all tokens owned by `owner`. O(owned) via the `OwnerTokens` index rather than a
+    /// full scan over `Tokens`.
+    pub fn tokens_of(owner: T::AccountId) -> Vec<TokenId> {
+        <OwnerTokens<T>>::iter_prefix(&owner).map(|(id, _)| id).collect()
+    }
 }
\ No newline at end of file
@@ -6,11 +6,17 @@ mod tests;
 
 use chainbridge as bridge;
 use example_erc721 as erc721;
-use frame_support::traits::{Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get};
-use frame_support::{decl_error, decl_event, decl_module, dispatch::DispatchResult, ensure};
-use frame_system::{self as system, ensure_signed};
+use pallet_assets as assets;
+use codec::{Decode, Encode};
+use frame_support::traits::{
+    tokens::fungibles::{Mutate, Transfer},
+    Currency, EnsureOrigin, ExistenceRequirement::AllowDeath, Get,
+};
+use frame_support::{decl_error, decl_event, decl_module, dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo}, ensure};
+use frame_system::{self as system, ensure_root, ensure_signed};
 use sp_arithmetic::traits::SaturatedConversion;
 use sp_core::U256;
+use sp_runtime::{traits::Zero, RuntimeDebug};
 use sp_std::prelude::*;
 
 pub use pallet::*;
@@ -20,6 +26,28 @@ type ResourceId = bridge::ResourceId;
 type BalanceOf<T> =
 <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+type AssetIdOf<T> = <T as assets::Config>::AssetId;
+type AssetBalanceOf<T> = <T as assets::Config>::Balance;
+
+/// The largest `|local_decimals - remote_decimals|` we'll scale by. `U256::pow` doesn't
+/// check for overflow, so this keeps `10^delta` comfortably within `U256::MAX` (~1.16e77) with
+/// plenty of headroom left for the amount itself, rather than silently wrapping or panicking.
+const MAX_DECIMAL_DELTA: u8 = 30;
+
+/// Describes how a bridgeable fungible asset relates to the chain it is registered on.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct AssetRegistryInfo<AssetId> {
+    /// The `pallet-assets` identifier backing this resource on this chain.
+    pub local_asset_id: AssetId,
+    /// The chain that holds the canonical reserve for this asset, if not this chain.
+    /// `None` means this chain is the reserve: outbound transfers lock, inbound transfers unlock.
+    /// `Some(chain_id)` means this chain only ever mints/burns a representation of the asset.
+    pub reserve_location: Option<bridge::ChainId>,
+    /// Destination chains this resource is allowed to move to. Only outbound transfers are
+    /// checked against this list; inbound `transfer_asset` calls do not consult it.
+    pub enabled_chains: Vec<bridge::ChainId>,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::pallet_prelude::*;
@@ -31,15 +59,35 @@ pub mod pallet {
     #[pallet::metadata(<T as frame_system::Config>::Hash = "Hash")]
     pub enum Event<T: Config> {
         Remark(<T as frame_system::Config>::Hash),
+        /// A fungible asset resource was registered for bridging
+        ResourceRegistered(ResourceId),
+        /// The bridge fee for a destination chain was updated
+        BridgeFeeSet(bridge::ChainId, BalanceOf<T>),
     }
 
     #[pallet::error]
     pub enum Error<T> {
         InvalidTransfer,
+        /// No `AssetRegistryInfo` exists for this resource id
+        ResourceNotRegistered,
+        /// The resource is not permitted to move to/from this chain
+        ChainNotEnabled,
+        /// Scaling the amount down to the remote chain's decimals would drop non-zero digits
+        PrecisionLoss,
+        /// Scaling the amount up to the remote chain's decimals would overflow
+        AmountOverflow,
+        /// The signed origin does not hold enough of the native currency to pay the bridge fee
+        InsufficientFee,
+        /// The bridge fee payment to the fee collector failed
+        FeeTransferFailed,
+        /// A non-zero bridge fee can't be set until a fee collector account is configured
+        NoFeeCollector,
+        /// The gap between `local_decimals` and `remote_decimals` is too large to scale safely
+        DecimalDeltaTooLarge,
     }
 
     #[pallet::config]
-    pub trait Config: frame_system::Config + bridge::Config + erc721::Config {
+    pub trait Config: frame_system::Config + bridge::Config + erc721::Config + assets::Config {
 
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
         /// Specifies the origin check provided by the bridge for calls that can only be called by the bridge pallet
@@ -54,6 +102,43 @@ pub mod pallet {
         type Erc721Id: Get<ResourceId>;
     }
 
+    /// Registry of bridgeable `pallet-assets` backed fungible assets, keyed by resource id.
+    #[pallet::storage]
+    #[pallet::getter(fn resources)]
+    pub(super) type Resources<T: Config> = StorageMap<
+        _,
+        Blake2_256,
+        ResourceId,
+        AssetRegistryInfo<AssetIdOf<T>>
+    >;
+
+    /// Per-resource `(local_decimals, remote_decimals)`, used to rescale amounts crossing chains
+    /// with different decimal precision. A resource with no entry is assumed to match 1:1.
+    #[pallet::storage]
+    #[pallet::getter(fn resource_decimals)]
+    pub(super) type ResourceDecimals<T: Config> = StorageMap<
+        _,
+        Blake2_256,
+        ResourceId,
+        (u8, u8)
+    >;
+
+    /// The flat fee charged for an outbound transfer to a given destination chain. Chains with
+    /// no entry are free to use.
+    #[pallet::storage]
+    #[pallet::getter(fn bridge_fees)]
+    pub(super) type BridgeFees<T: Config> = StorageMap<
+        _,
+        Blake2_256,
+        bridge::ChainId,
+        BalanceOf<T>
+    >;
+
+    /// The account that collects bridge fees.
+    #[pallet::storage]
+    #[pallet::getter(fn fee_collector)]
+    pub(super) type FeeCollector<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
     // NOTE: if the visibility of trait store is private but you want to make it available
@@ -70,12 +155,15 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Transfers an arbitrary hash to a (whitelisted) destination chain.
         #[pallet::weight(195_000_000)]
+        #[frame_support::transactional]
         pub fn transfer_hash(
             origin: OriginFor<T>,
             hash: T::Hash,
             dest_id: bridge::ChainId
         ) -> DispatchResultWithPostInfo {
-            ensure_signed(origin)?;
+            let source = ensure_signed(origin)?;
+            ensure!(<bridge::Pallet<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
+            Self::collect_bridge_fee(&source, dest_id)?;
 
             let resource_id = T::HashId::get();
             let metadata: Vec<u8> = hash.as_ref().to_vec();
@@ -84,6 +172,7 @@ pub mod pallet {
 
         /// Transfers some amount of the native token to some recipient on a (whitelisted) destination chain.
         #[pallet::weight(195_000_000)]
+        #[frame_support::transactional]
         pub fn transfer_native(
             origin: OriginFor<T>,
             amount: BalanceOf<T>,
@@ -92,15 +181,53 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let source = ensure_signed(origin)?;
             ensure!(<bridge::Pallet<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
+            Self::collect_bridge_fee(&source, dest_id)?;
             let bridge_id = <bridge::Pallet<T>>::account_id();
             T::Currency::transfer(&source, &bridge_id, amount.into(), AllowDeath)?;
 
             let resource_id = T::NativeTokenId::get();
-            <bridge::Pallet<T>>::transfer_fungible(dest_id, resource_id, recipient, U256::from(amount.saturated_into::<u128>()))
+            let remote_amount = Self::convert_to_remote_decimals(resource_id, U256::from(amount.saturated_into::<u128>()))?;
+            <bridge::Pallet<T>>::transfer_fungible(dest_id, resource_id, recipient, remote_amount)
+        }
+
+        /// Transfers some amount of a `pallet-assets` backed resource to some recipient on a
+        /// (whitelisted) destination chain. If this chain is the asset's reserve, the amount is
+        /// locked with the bridge account; otherwise it is burned from the caller.
+        #[pallet::weight(195_000_000)]
+        #[frame_support::transactional]
+        pub fn transfer_fungible_asset(
+            origin: OriginFor<T>,
+            r_id: ResourceId,
+            amount: AssetBalanceOf<T>,
+            recipient: Vec<u8>,
+            dest_id: bridge::ChainId
+        ) -> DispatchResultWithPostInfo {
+            let source = ensure_signed(origin)?;
+            ensure!(<bridge::Pallet<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
+            let info = Self::resources(r_id).ok_or(Error::<T>::ResourceNotRegistered)?;
+            ensure!(info.enabled_chains.contains(&dest_id), Error::<T>::ChainNotEnabled);
+            Self::collect_bridge_fee(&source, dest_id)?;
+
+            if info.reserve_location.is_none() {
+                // This chain is the reserve: lock funds with the bridge account.
+                let bridge_id = <bridge::Pallet<T>>::account_id();
+                <assets::Pallet<T> as Transfer<T::AccountId>>::transfer(
+                    info.local_asset_id, &source, &bridge_id, amount, false
+                )?;
+            } else {
+                // This chain only holds a representation: burn it from the caller.
+                <assets::Pallet<T> as Mutate<T::AccountId>>::burn_from(
+                    info.local_asset_id, &source, amount
+                )?;
+            }
+
+            let remote_amount = Self::convert_to_remote_decimals(r_id, U256::from(amount.saturated_into::<u128>()))?;
+            <bridge::Pallet<T>>::transfer_fungible(dest_id, r_id, recipient, remote_amount)
         }
 
         /// Transfer a non-fungible token (erc721) to a (whitelisted) destination chain.
         #[pallet::weight(195_000_000)]
+        #[frame_support::transactional]
         pub fn transfer_erc721(
             origin: OriginFor<T>,
             recipient: Vec<u8>,
@@ -109,13 +236,16 @@ pub mod pallet {
         ) -> DispatchResultWithPostInfo {
             let source = ensure_signed(origin)?;
             ensure!(<bridge::Pallet<T>>::chain_whitelisted(dest_id), Error::<T>::InvalidTransfer);
+            Self::collect_bridge_fee(&source, dest_id)?;
             match <erc721::Module<T>>::tokens(&token_id) {
                 Some(token) => {
-                    <erc721::Module<T>>::burn_token(source, token_id)?;
                     let resource_id = T::Erc721Id::get();
                     let tid: &mut [u8] = &mut[0; 32];
                     token_id.to_big_endian(tid);
-                    <bridge::Pallet<T>>::transfer_nonfungible(dest_id, resource_id, tid.to_vec(), recipient, token.metadata)
+                    let tid = tid.to_vec();
+                    Self::burn_then_dispatch(source, token_id, || {
+                        <bridge::Pallet<T>>::transfer_nonfungible(dest_id, resource_id, tid, recipient, token.metadata)
+                    })
                 }
                 None => Err(Error::<T>::InvalidTransfer)?
             }
@@ -125,16 +255,109 @@ pub mod pallet {
         // Executable calls. These can be triggered by a bridge transfer initiated on another chain
         //
 
-        /// Executes a simple currency transfer using the bridge account as the source
+        /// Executes a simple currency transfer using the bridge account as the source. `amount`
+        /// is denominated in the remote chain's decimals and is rescaled to this chain's
+        /// decimals for `r_id` before the transfer is made.
         #[pallet::weight(195_000_000)]
         pub fn transfer(
             origin: OriginFor<T>,
             to: T::AccountId,
-            amount: BalanceOf<T>,
+            amount: U256,
             r_id: ResourceId
         ) -> DispatchResultWithPostInfo {
             let source = T::BridgeOrigin::ensure_origin(origin)?;
-            <T as Config>::Currency::transfer(&source, &to, amount.into(), AllowDeath)?;
+            let local_amount = Self::convert_to_local_decimals(r_id, amount)?;
+            let local_amount: BalanceOf<T> = local_amount.saturated_into::<u128>().saturated_into();
+            <T as Config>::Currency::transfer(&source, &to, local_amount, AllowDeath)?;
+            Ok(().into())
+        }
+
+        /// Executes a `pallet-assets` backed transfer using the bridge account as the source.
+        /// Mirrors `transfer_fungible_asset`: unlocks from the bridge account if this chain is
+        /// the asset's reserve, otherwise mints a fresh representation for the recipient.
+        #[pallet::weight(195_000_000)]
+        pub fn transfer_asset(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            amount: U256,
+            r_id: ResourceId
+        ) -> DispatchResultWithPostInfo {
+            T::BridgeOrigin::ensure_origin(origin)?;
+            let info = Self::resources(r_id).ok_or(Error::<T>::ResourceNotRegistered)?;
+            let local_amount = Self::convert_to_local_decimals(r_id, amount)?;
+            let local_amount: AssetBalanceOf<T> = local_amount.saturated_into::<u128>().saturated_into();
+
+            if info.reserve_location.is_none() {
+                let bridge_id = <bridge::Pallet<T>>::account_id();
+                <assets::Pallet<T> as Transfer<T::AccountId>>::transfer(
+                    info.local_asset_id, &bridge_id, &to, local_amount, false
+                )?;
+            } else {
+                <assets::Pallet<T> as Mutate<T::AccountId>>::mint_into(
+                    info.local_asset_id, &to, local_amount
+                )?;
+            }
+
+            Ok(().into())
+        }
+
+        /// Registers a `pallet-assets` backed resource for bridging.
+        #[pallet::weight(195_000_000)]
+        pub fn register_resource(
+            origin: OriginFor<T>,
+            r_id: ResourceId,
+            info: AssetRegistryInfo<AssetIdOf<T>>
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            for chain_id in info.enabled_chains.iter() {
+                ensure!(<bridge::Pallet<T>>::chain_whitelisted(*chain_id), Error::<T>::ChainNotEnabled);
+            }
+
+            <Resources<T>>::insert(&r_id, info);
+            Self::deposit_event(Event::ResourceRegistered(r_id));
+            Ok(().into())
+        }
+
+        /// Sets the local/remote decimal precision for a resource, used to rescale amounts
+        /// crossing chains with different decimal precision.
+        #[pallet::weight(195_000_000)]
+        pub fn set_resource_decimals(
+            origin: OriginFor<T>,
+            r_id: ResourceId,
+            local_decimals: u8,
+            remote_decimals: u8
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            let delta = local_decimals.max(remote_decimals) - local_decimals.min(remote_decimals);
+            ensure!(delta <= MAX_DECIMAL_DELTA, Error::<T>::DecimalDeltaTooLarge);
+            <ResourceDecimals<T>>::insert(&r_id, (local_decimals, remote_decimals));
+            Ok(().into())
+        }
+
+        /// Sets the flat fee charged for outbound transfers to `dest_id`.
+        #[pallet::weight(195_000_000)]
+        pub fn set_bridge_fee(
+            origin: OriginFor<T>,
+            dest_id: bridge::ChainId,
+            fee: BalanceOf<T>
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            if !fee.is_zero() {
+                ensure!(<FeeCollector<T>>::exists(), Error::<T>::NoFeeCollector);
+            }
+            <BridgeFees<T>>::insert(&dest_id, fee);
+            Self::deposit_event(Event::BridgeFeeSet(dest_id, fee));
+            Ok(().into())
+        }
+
+        /// Sets the account that collects bridge fees.
+        #[pallet::weight(195_000_000)]
+        pub fn set_fee_collector(
+            origin: OriginFor<T>,
+            collector: T::AccountId
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            <FeeCollector<T>>::put(collector);
             Ok(().into())
         }
 
@@ -149,7 +372,12 @@ pub mod pallet {
             Ok(().into())
         }
 
-        /// Allows the bridge to issue new erc721 tokens
+        /// Allows the bridge to issue new erc721 tokens. `r_id` doubles as the erc721 collection
+        /// id. Operators should call `erc721::create_collection` for every resource bridged in
+        /// so wallets see real name/symbol metadata; if that was missed, the mint still succeeds
+        /// into an empty placeholder collection rather than failing outright, since by this
+        /// point the token has already been burned on the source chain and there is no way to
+        /// give it back.
         #[pallet::weight(195_000_000)]
         pub fn mint_erc721(
             origin: OriginFor<T>,
@@ -159,8 +387,79 @@ pub mod pallet {
             r_id: ResourceId
         ) -> DispatchResultWithPostInfo {
             T::BridgeOrigin::ensure_origin(origin)?;
-            <erc721::Module<T>>::mint_token(recipient, id, metadata)?;
+            <erc721::Module<T>>::mint_token_or_create_collection(recipient, r_id, id, metadata)?;
             Ok(().into())
         }
     }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Burns `token_id` from `source` and then runs `dispatch` (the bridge call that emits the
+    /// cross-chain message), rolling back the burn if `dispatch` errors. Used by
+    /// `transfer_erc721`; pulled out as its own `#[transactional]` unit so the regression test
+    /// can drive the exact burn-then-dispatch sequence with an injected failing dispatch,
+    /// without needing the real `chainbridge` pallet's dispatch to be made to fail.
+    #[frame_support::transactional]
+    pub(crate) fn burn_then_dispatch(
+        source: T::AccountId,
+        token_id: U256,
+        dispatch: impl FnOnce() -> DispatchResultWithPostInfo
+    ) -> DispatchResultWithPostInfo {
+        <erc721::Module<T>>::burn_token(source, token_id)?;
+        dispatch()
+    }
+
+    /// Charges `source` the configured bridge fee for `dest_id`, paying it to the fee collector.
+    /// A chain with no configured fee is free. Fails the whole call if the fee can't be paid.
+    fn collect_bridge_fee(source: &T::AccountId, dest_id: bridge::ChainId) -> DispatchResult {
+        let fee = match Self::bridge_fees(dest_id) {
+            Some(fee) if !fee.is_zero() => fee,
+            _ => return Ok(()),
+        };
+        let collector = Self::fee_collector().ok_or(Error::<T>::FeeTransferFailed)?;
+        ensure!(T::Currency::free_balance(source) >= fee, Error::<T>::InsufficientFee);
+        T::Currency::transfer(source, &collector, fee, AllowDeath)
+            .map_err(|_| Error::<T>::FeeTransferFailed)?;
+        Ok(())
+    }
+
+    /// Rescales `amount` (denominated in this chain's decimals) into the destination chain's
+    /// decimals for `resource_id`. A resource with no `ResourceDecimals` entry passes through
+    /// unscaled.
+    ///
+    /// Rejects amounts that would lose non-zero low-order digits when scaling down, and guards
+    /// against overflow when scaling up.
+    pub fn convert_to_remote_decimals(resource_id: ResourceId, amount: U256) -> Result<U256, DispatchError> {
+        let (local_decimals, remote_decimals) = match Self::resource_decimals(resource_id) {
+            Some(decimals) => decimals,
+            None => return Ok(amount),
+        };
+
+        if remote_decimals >= local_decimals {
+            let factor = U256::from(10u128).pow(U256::from(remote_decimals - local_decimals));
+            amount.checked_mul(factor).ok_or_else(|| Error::<T>::AmountOverflow.into())
+        } else {
+            let factor = U256::from(10u128).pow(U256::from(local_decimals - remote_decimals));
+            ensure!(amount % factor == U256::zero(), Error::<T>::PrecisionLoss);
+            Ok(amount / factor)
+        }
+    }
+
+    /// The inverse of [`Self::convert_to_remote_decimals`]: rescales an inbound amount
+    /// (denominated in the remote chain's decimals) into this chain's decimals.
+    pub fn convert_to_local_decimals(resource_id: ResourceId, amount: U256) -> Result<U256, DispatchError> {
+        let (local_decimals, remote_decimals) = match Self::resource_decimals(resource_id) {
+            Some(decimals) => decimals,
+            None => return Ok(amount),
+        };
+
+        if local_decimals >= remote_decimals {
+            let factor = U256::from(10u128).pow(U256::from(local_decimals - remote_decimals));
+            amount.checked_mul(factor).ok_or_else(|| Error::<T>::AmountOverflow.into())
+        } else {
+            let factor = U256::from(10u128).pow(U256::from(remote_decimals - local_decimals));
+            ensure!(amount % factor == U256::zero(), Error::<T>::PrecisionLoss);
+            Ok(amount / factor)
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,156 @@
+#![cfg(test)]
+
+use crate as example_pallet;
+use crate::Config;
+use frame_support::{parameter_types, PalletId};
+use frame_system::{self as system, EnsureSignedBy};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+};
+
+pub use pallet_balances as balances;
+pub use chainbridge as bridge;
+pub use example_erc721 as erc721;
+pub use pallet_assets as assets;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+        Bridge: bridge::{Pallet, Call, Storage, Event<T>},
+        Erc721: erc721::{Pallet, Call, Storage, Event<T>},
+        Assets: assets::{Pallet, Call, Storage, Event<T>},
+        ExamplePallet: example_pallet::{Pallet, Call, Event<T>},
+    }
+);
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+}
+
+impl system::Config for Test {
+    type BaseCallFilter = frame_support::traits::Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl balances::Config for Test {
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u64;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const AssetDeposit: u64 = 0;
+    pub const ApprovalDeposit: u64 = 0;
+    pub const StringLimit: u32 = 50;
+    pub const MetadataDepositBase: u64 = 0;
+    pub const MetadataDepositPerByte: u64 = 0;
+}
+
+impl assets::Config for Test {
+    type Event = Event;
+    type Balance = u64;
+    type AssetId = u32;
+    type Currency = Balances;
+    type ForceOrigin = frame_system::EnsureRoot<u64>;
+    type AssetDeposit = AssetDeposit;
+    type MetadataDepositBase = MetadataDepositBase;
+    type MetadataDepositPerByte = MetadataDepositPerByte;
+    type ApprovalDeposit = ApprovalDeposit;
+    type StringLimit = StringLimit;
+    type Freezer = ();
+    type Extra = ();
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const ChainId: u8 = 5;
+    pub const ProposalLifetime: u64 = 50;
+}
+
+impl bridge::Config for Test {
+    type Event = Event;
+    type BridgeChainId = ChainId;
+    type Proposal = Call;
+    type ProposalLifetime = ProposalLifetime;
+}
+
+parameter_types! {
+    pub Erc721Identifier: [u8; 32] = [1u8; 32];
+}
+
+impl erc721::Config for Test {
+    type Event = Event;
+    type Identifier = Erc721Identifier;
+}
+
+parameter_types! {
+    pub HashId: bridge::ResourceId = [0u8; 32];
+    pub NativeTokenId: bridge::ResourceId = [1u8; 32];
+    pub Erc721Id: bridge::ResourceId = [2u8; 32];
+}
+
+impl Config for Test {
+    type Event = Event;
+    type BridgeOrigin = bridge::EnsureBridge<Test>;
+    type Currency = Balances;
+    type HashId = HashId;
+    type NativeTokenId = NativeTokenId;
+    type Erc721Id = Erc721Id;
+}
+
+pub const RELAYER_A: u64 = 0x2;
+pub const ENDOWED_BALANCE: u64 = 100_000_000;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let bridge_id = bridge::Pallet::<Test>::account_id();
+    let mut t = system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+    balances::GenesisConfig::<Test> {
+        balances: vec![(bridge_id, ENDOWED_BALANCE), (RELAYER_A, ENDOWED_BALANCE)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+    sp_io::TestExternalities::new(t)
+}
@@ -0,0 +1,86 @@
+#![cfg(test)]
+
+use crate::mock::{new_test_ext, bridge, Erc721, Origin, RELAYER_A, Test};
+use crate as example_pallet;
+use example_erc721 as erc721;
+use frame_support::assert_noop;
+use sp_core::U256;
+
+const ERC721_COLLECTION_ID: [u8; 32] = [7u8; 32];
+const TOKEN_ID: U256 = U256([1, 0, 0, 0]);
+const DEST_ID: bridge::ChainId = 99;
+
+fn collection_props() -> erc721::CollectionProperties {
+    erc721::CollectionProperties { name: b"Test".to_vec(), symbol: b"TST".to_vec(), base_uri: b"".to_vec() }
+}
+
+#[test]
+fn transfer_erc721_pre_burn_guard_rejects_non_whitelisted_destination() {
+    new_test_ext().execute_with(|| {
+        erc721::Pallet::<Test>::create_collection(Origin::root(), ERC721_COLLECTION_ID, collection_props()).unwrap();
+        erc721::Pallet::<Test>::mint(
+            Origin::root(), RELAYER_A, ERC721_COLLECTION_ID, TOKEN_ID, b"metadata".to_vec(),
+        )
+        .unwrap();
+
+        // Destination chain 99 is never whitelisted with the bridge pallet, so `transfer_erc721`
+        // rejects it before ever reaching `burn_token`. This only covers the pre-burn guard, not
+        // the `#[transactional]` rollback guarantee - see
+        // `burn_then_dispatch_rolls_back_burn_when_the_bridge_dispatch_fails` for that.
+        assert_noop!(
+            example_pallet::Pallet::<Test>::transfer_erc721(
+                Origin::signed(RELAYER_A), b"recipient".to_vec(), TOKEN_ID, DEST_ID,
+            ),
+            example_pallet::Error::<Test>::InvalidTransfer
+        );
+
+        assert_eq!(Erc721::owner_of(TOKEN_ID), Some(RELAYER_A));
+        assert!(Erc721::tokens(TOKEN_ID).is_some());
+    });
+}
+
+#[test]
+fn burn_then_dispatch_rolls_back_burn_when_the_bridge_dispatch_fails() {
+    new_test_ext().execute_with(|| {
+        erc721::Pallet::<Test>::create_collection(Origin::root(), ERC721_COLLECTION_ID, collection_props()).unwrap();
+        erc721::Pallet::<Test>::mint(
+            Origin::root(), RELAYER_A, ERC721_COLLECTION_ID, TOKEN_ID, b"metadata".to_vec(),
+        )
+        .unwrap();
+
+        // `chainbridge`'s own `transfer_nonfungible` only ever rejects a non-whitelisted
+        // `dest_id`, a guard `transfer_erc721` already checks *before* calling
+        // `burn_then_dispatch` - so there's no way to make the live `chainbridge` dispatch itself
+        // fail *after* the burn from outside the pallet. `burn_then_dispatch` is the
+        // `#[transactional]` unit `transfer_erc721` actually uses for its burn-then-dispatch
+        // step, so drive it directly with an injected failing dispatch closure, standing in for
+        // any bridge call (current or future) that can fail post-burn, and assert the burn rolls
+        // back with it.
+        assert_noop!(
+            example_pallet::Pallet::<Test>::burn_then_dispatch(
+                RELAYER_A, TOKEN_ID, || Err(example_pallet::Error::<Test>::InvalidTransfer.into()),
+            ),
+            example_pallet::Error::<Test>::InvalidTransfer
+        );
+
+        assert_eq!(Erc721::owner_of(TOKEN_ID), Some(RELAYER_A));
+        assert!(Erc721::tokens(TOKEN_ID).is_some());
+    });
+}
+
+#[test]
+fn burn_token_decrements_token_count() {
+    new_test_ext().execute_with(|| {
+        erc721::Pallet::<Test>::create_collection(
+            Origin::root(),
+            ERC721_COLLECTION_ID,
+            erc721::CollectionProperties { name: b"Test".to_vec(), symbol: b"TST".to_vec(), base_uri: b"".to_vec() },
+        )
+        .unwrap();
+        erc721::Pallet::<Test>::mint_token(RELAYER_A, ERC721_COLLECTION_ID, TOKEN_ID, b"metadata".to_vec()).unwrap();
+        assert_eq!(Erc721::token_count(), U256::one());
+
+        erc721::Pallet::<Test>::burn_token(RELAYER_A, TOKEN_ID).unwrap();
+        assert_eq!(Erc721::token_count(), U256::zero());
+    });
+}